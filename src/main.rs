@@ -1,13 +1,24 @@
+use bevy::core::FixedTimestep;
 use bevy::ecs::schedule::ShouldRun;
 use bevy::prelude::*;
 use bevy_egui::{egui, EguiContext, EguiPlugin};
 use egui::plot::{Bar, BarChart, Line, Plot, Value, Values};
 use egui::widgets::DragValue;
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 const MOVEMENT_SPEED: f32 = 150.;
 const DAY_LENGTH: f32 = 10.;
 const NIGHT_LENGTH: f32 = 2.;
+const PHEROMONE_GRID_SIZE: usize = 32;
+const PHEROMONE_DEPOSIT: f32 = 1.;
+const PHEROMONE_MAX: f32 = 10.;
+const PHEROMONE_DECAY: f32 = 0.995;
+const PHEROMONE_BIAS_STRENGTH: f32 = 0.3;
+const HISTORY_CAPACITY: usize = 20;
+const FIXED_TIMESTEP: f64 = 1. / 60.;
 
 struct Sunset(bool);
 struct DayTimer(Timer);
@@ -18,8 +29,74 @@ struct Charts {
     food_count: Vec<Bar>,
     avg_speed: Vec<Value>,
     avg_sense: Vec<Value>,
+    predator_population: Vec<Bar>,
+    avg_predator_speed: Vec<Value>,
 }
-struct Started(bool);
+struct Loaded(bool);
+
+/// The simulation's top-level screen. `Configuring` shows the options window
+/// and waits for "Start Simulation" or a load; `Running` steps the
+/// simulation; `Paused` is pushed on top of `Running` by [`pause_toggle`] so
+/// popping it resumes exactly where the simulation left off; `Extinct`
+/// freezes everything once the population hits zero and shows the
+/// restart/quit screen.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+enum GameState {
+    Configuring,
+    Running,
+    Paused,
+    Extinct,
+}
+
+/// Summary statistics for the current run, shown on the extinction screen.
+#[derive(Default)]
+struct RunStats {
+    generations: u32,
+    peak_population: i32,
+    final_avg_speed: f32,
+    final_avg_sense: f32,
+}
+struct SimRng(StdRng);
+
+impl SimRng {
+    fn from_seed(seed: u32) -> Self {
+        Self(StdRng::seed_from_u64(seed as u64))
+    }
+}
+struct SaveFile(String);
+
+impl Default for SaveFile {
+    fn default() -> Self {
+        Self(String::from("save.ron"))
+    }
+}
+struct Pheromones {
+    grid: [[f32; PHEROMONE_GRID_SIZE]; PHEROMONE_GRID_SIZE],
+}
+
+impl Default for Pheromones {
+    fn default() -> Self {
+        Self {
+            grid: [[0.; PHEROMONE_GRID_SIZE]; PHEROMONE_GRID_SIZE],
+        }
+    }
+}
+
+impl Pheromones {
+    fn deposit(&mut self, window: &Window, x: f32, y: f32) {
+        let (col, row) = pheromone_cell(window, x, y);
+        self.grid[col][row] = (self.grid[col][row] + PHEROMONE_DEPOSIT).min(PHEROMONE_MAX);
+    }
+
+    fn decay(&mut self, factor: f32) {
+        for column in self.grid.iter_mut() {
+            for cell in column.iter_mut() {
+                *cell *= factor;
+            }
+        }
+    }
+}
+#[derive(Clone, Serialize, Deserialize)]
 struct Options {
     simulation_speed: f32,
     movement_speed: f32,
@@ -31,6 +108,11 @@ struct Options {
     trait_change_intensity: f32,
     person_count: i32,
     food_count: i32,
+    predator_count: i32,
+    predator_speed_cost: f32,
+    predator_sense_cost: f32,
+    predator_catch_radius: f32,
+    rng_seed: u32,
 }
 
 impl Default for Options {
@@ -46,6 +128,11 @@ impl Default for Options {
             trait_change_intensity: 0.1,
             person_count: 10,
             food_count: 100,
+            predator_count: 3,
+            predator_speed_cost: 1. / (NIGHT_LENGTH + DAY_LENGTH) / MOVEMENT_SPEED,
+            predator_sense_cost: 1. / (NIGHT_LENGTH + DAY_LENGTH),
+            predator_catch_radius: 45.,
+            rng_seed: 42,
         }
     }
 }
@@ -53,28 +140,142 @@ impl Default for Options {
 struct RandomizeDirections;
 struct SpawnFood;
 struct Reproduce(Transform, Traits);
+struct ReproducePredator(Transform, Traits);
+struct SaveRequested;
+struct LoadRequested;
+
+#[derive(Serialize, Deserialize)]
+struct ChartPoint {
+    x: f64,
+    y: f64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct AgentSnapshot {
+    x: f32,
+    y: f32,
+    rotation: f32,
+    energy: f32,
+    traits: Traits,
+    goal: AIGoal,
+    hungry: bool,
+    fertile: bool,
+}
+
+/// A checkpoint of a run. Reconstructs the trait distribution, positions,
+/// energy and daily-plan progress of every agent exactly, so a reload
+/// resumes the same day at the same phase. It does not hold [`SimRng`]'s
+/// internal stream position — [`load_simulation`] reseeds it from
+/// `options.rng_seed`, so the RNG draws after a load diverge from what the
+/// original run would have drawn at that point. Food, the pheromone grid,
+/// path histories, chase targets and the run statistics are not saved
+/// either: a load despawns all food and none spawns until the next night
+/// ends.
+#[derive(Serialize, Deserialize)]
+struct SimulationSnapshot {
+    options: Options,
+    sunset: bool,
+    day_timer_elapsed: f32,
+    night_timer_elapsed: f32,
+    population: Vec<ChartPoint>,
+    food_count: Vec<ChartPoint>,
+    avg_speed: Vec<ChartPoint>,
+    avg_sense: Vec<ChartPoint>,
+    predator_population: Vec<ChartPoint>,
+    avg_predator_speed: Vec<ChartPoint>,
+    persons: Vec<AgentSnapshot>,
+    predators: Vec<AgentSnapshot>,
+}
 
 #[derive(Component)]
 struct Person;
+#[derive(Component)]
+struct Predator;
 #[derive(Component, Debug)]
 struct Hungry;
 #[derive(Component)]
 struct Fertile;
-#[derive(Component)]
-struct Returning;
-#[derive(Component)]
-struct AtHome;
-#[derive(Component)]
-struct Dead;
+
+/// The phase of a person/predator's daily plan. Replaces the old
+/// `Returning`/`AtHome`/`Dead` marker components so an entity can't be in two
+/// phases at once; `Hungry` and `Fertile` remain separate, as they're
+/// feeding-progress modifiers rather than phases of the plan.
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+enum AIGoal {
+    Seek,
+    Return,
+    AtHome,
+    Dead,
+}
+
+impl AIGoal {
+    /// Moves to `next`, panicking in debug builds on a transition that isn't
+    /// part of the daily plan. A single choke point for every phase change
+    /// means a bug that skips a phase (e.g. `Seek` straight to `AtHome`) is
+    /// caught immediately instead of silently desyncing the simulation.
+    fn transition(&mut self, next: AIGoal) {
+        let legal = matches!(
+            (*self, next),
+            (AIGoal::Seek, AIGoal::Return)
+                | (AIGoal::Return, AIGoal::AtHome)
+                | (AIGoal::AtHome, AIGoal::Seek)
+                | (_, AIGoal::Dead)
+        );
+        debug_assert!(legal, "illegal AIGoal transition: {:?} -> {:?}", self, next);
+        *self = next;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn daily_plan_transitions_are_legal() {
+        let mut goal = AIGoal::Seek;
+        goal.transition(AIGoal::Return);
+        assert_eq!(goal, AIGoal::Return);
+        goal.transition(AIGoal::AtHome);
+        assert_eq!(goal, AIGoal::AtHome);
+        goal.transition(AIGoal::Seek);
+        assert_eq!(goal, AIGoal::Seek);
+        goal.transition(AIGoal::Dead);
+        assert_eq!(goal, AIGoal::Dead);
+    }
+
+    #[test]
+    #[should_panic(expected = "illegal AIGoal transition")]
+    fn skipping_a_phase_panics() {
+        let mut goal = AIGoal::Seek;
+        goal.transition(AIGoal::AtHome);
+    }
+}
+
 #[derive(Component)]
 struct Energy(f32);
+#[derive(Component, Default)]
+struct History(Vec<Transform>);
+
+impl History {
+    fn push(&mut self, transform: Transform) {
+        self.0.push(transform);
+        if self.0.len() > HISTORY_CAPACITY {
+            self.0.remove(0);
+        }
+    }
+}
 #[derive(Component)]
 struct Prey {
     x: f32,
     y: f32,
     distance: f32,
 }
-#[derive(Component, Copy, Clone)]
+#[derive(Component)]
+struct Hunt {
+    x: f32,
+    y: f32,
+}
+#[derive(Component, Copy, Clone, Serialize, Deserialize)]
 struct Traits {
     speed: f32,
     sense: f32,
@@ -90,11 +291,10 @@ impl Default for Traits {
 }
 
 impl Traits {
-    fn variation(&self, change_intensity: f32) -> Traits {
+    fn variation(&self, change_intensity: f32, rng: &mut impl Rng) -> Traits {
         Traits {
-            speed: self.speed * (1. + (rand::random::<f32>() * 2. - 1.) * change_intensity),
-            sense: (self.sense * (1. + (rand::random::<f32>() * 2. - 1.) * change_intensity))
-                .max(1e5),
+            speed: self.speed * (1. + (rng.gen::<f32>() * 2. - 1.) * change_intensity),
+            sense: (self.sense * (1. + (rng.gen::<f32>() * 2. - 1.) * change_intensity)).max(1.),
         }
     }
 }
@@ -117,12 +317,75 @@ fn bar_options() -> Bar {
     }
 }
 
-fn get_random_location(window: &Window) -> Transform {
+fn pheromone_cell(window: &Window, x: f32, y: f32) -> (usize, usize) {
     let width = window.width();
     let height = window.height();
 
-    let x = (rand::random::<f32>() - 0.5) * width;
-    let y = (rand::random::<f32>() - 0.5) * height;
+    let col = (((x + width / 2.) / width) * PHEROMONE_GRID_SIZE as f32)
+        .clamp(0., PHEROMONE_GRID_SIZE as f32 - 1.) as usize;
+    let row = (((y + height / 2.) / height) * PHEROMONE_GRID_SIZE as f32)
+        .clamp(0., PHEROMONE_GRID_SIZE as f32 - 1.) as usize;
+
+    (col, row)
+}
+
+fn sample_best_neighbor(
+    pheromones: &Pheromones,
+    window: &Window,
+    transform: &Transform,
+    sense: f32,
+) -> Option<Vec2> {
+    let (col, row) = pheromone_cell(window, transform.translation.x, transform.translation.y);
+    let cell_width = window.width() / PHEROMONE_GRID_SIZE as f32;
+    let cell_height = window.height() / PHEROMONE_GRID_SIZE as f32;
+    let radius_cells = ((sense / cell_width.min(cell_height)).ceil() as isize)
+        .clamp(1, PHEROMONE_GRID_SIZE as isize);
+
+    let mut best: Option<(f32, Vec2)> = None;
+    for dc in -radius_cells..=radius_cells {
+        for dr in -radius_cells..=radius_cells {
+            if dc == 0 && dr == 0 {
+                continue;
+            }
+            let c = col as isize + dc;
+            let r = row as isize + dr;
+            if c < 0
+                || r < 0
+                || c >= PHEROMONE_GRID_SIZE as isize
+                || r >= PHEROMONE_GRID_SIZE as isize
+            {
+                continue;
+            }
+            let intensity = pheromones.grid[c as usize][r as usize];
+            if intensity <= 0. {
+                continue;
+            }
+            let cell_x = c as f32 * cell_width - window.width() / 2. + cell_width / 2.;
+            let cell_y = r as f32 * cell_height - window.height() / 2. + cell_height / 2.;
+            let distance = get_distance(
+                transform.translation.x,
+                transform.translation.y,
+                cell_x,
+                cell_y,
+            );
+            if distance > sense {
+                continue;
+            }
+            if best.map_or(true, |(best_intensity, _)| intensity > best_intensity) {
+                best = Some((intensity, Vec2::new(cell_x, cell_y)));
+            }
+        }
+    }
+
+    best.map(|(_, position)| position)
+}
+
+fn get_random_location(window: &Window, rng: &mut impl Rng) -> Transform {
+    let width = window.width();
+    let height = window.height();
+
+    let x = (rng.gen::<f32>() - 0.5) * width;
+    let y = (rng.gen::<f32>() - 0.5) * height;
 
     Transform::from_xyz(x, y, 0.)
 }
@@ -137,22 +400,48 @@ fn start_simulation(
     windows: Res<Windows>,
     mut ev_spawn_food: EventWriter<SpawnFood>,
     mut ev_randomize: EventWriter<RandomizeDirections>,
-    started: Res<Started>,
+    state: Res<State<GameState>>,
+    loaded: Res<Loaded>,
     mut day_timer: ResMut<DayTimer>,
     mut night_timer: ResMut<NightTimer>,
+    mut rng: ResMut<SimRng>,
     options: Res<Options>,
+    mut previous_state: Local<Option<GameState>>,
 ) {
-    if !started.is_changed() || !started.0 {
+    if !state.is_changed() {
+        return;
+    }
+    let previous = previous_state.replace(*state.current());
+    if *state.current() != GameState::Running
+        || previous != Some(GameState::Configuring)
+        || loaded.0
+    {
         return;
     };
+    *rng = SimRng::from_seed(options.rng_seed);
     for _i in 0..options.person_count {
         commands
             .spawn_bundle(SpriteBundle {
                 texture: asset_server.load("person1.png"),
-                transform: get_random_location(windows.primary()),
+                transform: get_random_location(windows.primary(), &mut rng.0),
                 ..default()
             })
             .insert(Person)
+            .insert(AIGoal::Seek)
+            .insert(Hungry)
+            .insert(Energy(options.base_energy))
+            .insert(Traits::default())
+            .insert(History::default());
+    }
+    for _i in 0..options.predator_count {
+        commands
+            .spawn_bundle(SpriteBundle {
+                texture: asset_server.load("predator1.png"),
+                transform: get_random_location(windows.primary(), &mut rng.0),
+                ..default()
+            })
+            .insert(Predator)
+            .insert(AIGoal::Seek)
             .insert(Hungry)
             .insert(Energy(options.base_energy))
             .insert(Traits::default());
@@ -161,11 +450,8 @@ fn start_simulation(
 
     ev_spawn_food.send(SpawnFood);
 
-    day_timer.0 = Timer::from_seconds(options.day_length / options.simulation_speed, true);
-    night_timer.0 = Timer::from_seconds(
-        (options.day_length + options.night_length) / options.simulation_speed,
-        true,
-    );
+    day_timer.0 = Timer::from_seconds(options.day_length, true);
+    night_timer.0 = Timer::from_seconds(options.day_length + options.night_length, true);
 }
 
 fn spawn_food(
@@ -173,6 +459,7 @@ fn spawn_food(
     asset_server: Res<AssetServer>,
     windows: Res<Windows>,
     mut events: EventReader<SpawnFood>,
+    mut rng: ResMut<SimRng>,
     options: Res<Options>,
 ) {
     for _event in events.iter() {
@@ -180,7 +467,7 @@ fn spawn_food(
             commands
                 .spawn_bundle(SpriteBundle {
                     texture: asset_server.load("food1.png"),
-                    transform: get_random_location(windows.primary()),
+                    transform: get_random_location(windows.primary(), &mut rng.0),
                     ..default()
                 })
                 .insert(Food)
@@ -189,195 +476,340 @@ fn spawn_food(
     }
 }
 
-fn normal_rotation(
-    mut sprites: Query<
-        (&mut Transform, Option<&Prey>),
-        (With<Person>, Without<Returning>, Without<Dead>),
-    >,
-    time: Res<Time>,
-    options: Res<Options>,
-) {
-    let mut rng = rand::thread_rng();
-    for (mut transform, prey) in sprites.iter_mut() {
-        if prey.is_some() {
-            transform.rotation = Quat::from_rotation_z(
-                (prey.unwrap().y - transform.translation.y)
-                    .atan2(prey.unwrap().x - transform.translation.x),
-            )
-        } else {
-            let rotation_delta = Quat::from_rotation_z(
-                (rng.gen::<f32>() - 0.5) * 12. * time.delta_seconds() * options.simulation_speed,
-            );
-            transform.rotation *= rotation_delta;
-        }
-    }
+fn get_distance(x1: f32, y1: f32, x2: f32, y2: f32) -> f32 {
+    ((x1 - x2).powi(2) + (y1 - y2).powi(2)).sqrt()
 }
 
-fn normal_movement(
-    time: Res<Time>,
-    mut sprites: Query<
-        (&mut Transform, &Traits, &mut Energy),
-        (With<Person>, Without<Returning>, Without<Dead>),
+fn decay_pheromones(mut pheromones: ResMut<Pheromones>) {
+    pheromones.decay(PHEROMONE_DECAY);
+}
+
+/// Dispatches a person's behavior for its current `AIGoal`. `Seek` searches
+/// for food until sunset, then stops moving; reaching a second food, or
+/// sunset once it has eaten at least once, advances it to `Return`, which
+/// walks it to the nearest edge of the map and advances it to `AtHome`. A
+/// person still `Hungry` at sunset stays in `Seek`.
+fn step_ai(
+    mut commands: Commands,
+    mut persons: Query<
+        (
+            Entity,
+            &mut AIGoal,
+            &mut Transform,
+            &mut History,
+            &mut Energy,
+            &Traits,
+            Option<&Prey>,
+        ),
+        With<Person>,
     >,
+    foods: Query<(Entity, &Transform), With<Food>>,
+    mut eaten: Query<&mut Eaten>,
+    hungry: Query<&Hungry>,
+    fertile: Query<&Fertile>,
+    sunset: Res<Sunset>,
     windows: Res<Windows>,
+    mut rng: ResMut<SimRng>,
+    mut pheromones: ResMut<Pheromones>,
     options: Res<Options>,
 ) {
-    for mut sprite in sprites.iter_mut() {
-        let rotation_rad = sprite.0.rotation.to_euler(EulerRot::ZYX).0;
-        let distance = options.movement_speed
-            * sprite.1.speed
-            * time.delta_seconds()
-            * options.simulation_speed;
-        let delta_x = distance * rotation_rad.cos();
-        let delta_y = distance * rotation_rad.sin();
-        let e = distance * sprite.1.speed * options.base_energy_cost
-            + options.sense_cost * time.delta_seconds() * options.simulation_speed;
-
-        sprite.2 .0 -= e;
-
-        sprite.0.translation.x += delta_x;
-        sprite.0.translation.y += delta_y;
-
-        let window = windows.primary();
-        let width = window.width() / 2.;
-        let height = window.height() / 2.;
-
-        if sprite.0.translation.x > width {
-            sprite.0.translation.x = -width;
-        }
-        if sprite.0.translation.x < -width {
-            sprite.0.translation.x = width;
-        }
-        if sprite.0.translation.y > height {
-            sprite.0.translation.y = -height;
-        }
-        if sprite.0.translation.y < -height {
-            sprite.0.translation.y = height;
+    let window = windows.primary();
+    let width = window.width() / 2.;
+    let height = window.height() / 2.;
+
+    for (entity, mut goal, mut transform, mut history, mut energy, traits, prey) in
+        persons.iter_mut()
+    {
+        match *goal {
+            AIGoal::AtHome | AIGoal::Dead => continue,
+            AIGoal::Seek => {
+                if sunset.0 {
+                    if !hungry.contains(entity) {
+                        goal.transition(AIGoal::Return);
+                    }
+                    continue;
+                }
+
+                if let Some(prey) = prey {
+                    transform.rotation = Quat::from_rotation_z(
+                        (prey.y - transform.translation.y).atan2(prey.x - transform.translation.x),
+                    );
+                } else if let Some(target) =
+                    sample_best_neighbor(&pheromones, window, &transform, traits.sense)
+                {
+                    let bias = Quat::from_rotation_z(
+                        (target.y - transform.translation.y)
+                            .atan2(target.x - transform.translation.x),
+                    );
+                    transform.rotation = transform.rotation.slerp(bias, PHEROMONE_BIAS_STRENGTH);
+                } else {
+                    let rotation_delta =
+                        Quat::from_rotation_z((rng.0.gen::<f32>() - 0.5) * 12. * FIXED_TIMESTEP as f32);
+                    transform.rotation *= rotation_delta;
+                }
+
+                let rotation_rad = transform.rotation.to_euler(EulerRot::ZYX).0;
+                let distance = options.movement_speed * traits.speed * FIXED_TIMESTEP as f32;
+                energy.0 -= distance * traits.speed * options.base_energy_cost
+                    + options.sense_cost * FIXED_TIMESTEP as f32;
+
+                transform.translation.x += distance * rotation_rad.cos();
+                transform.translation.y += distance * rotation_rad.sin();
+
+                if transform.translation.x > width {
+                    transform.translation.x = -width;
+                }
+                if transform.translation.x < -width {
+                    transform.translation.x = width;
+                }
+                if transform.translation.y > height {
+                    transform.translation.y = -height;
+                }
+                if transform.translation.y < -height {
+                    transform.translation.y = height;
+                }
+
+                history.push(*transform);
+
+                for food in foods.iter() {
+                    let food_distance = get_distance(
+                        transform.translation.x,
+                        transform.translation.y,
+                        food.1.translation.x,
+                        food.1.translation.y,
+                    );
+                    if food_distance <= 45. {
+                        if let Ok(mut is_eaten) = eaten.get_mut(food.0) {
+                            if !is_eaten.0 {
+                                commands.entity(food.0).despawn();
+                                commands.entity(food.0).remove::<Eaten>();
+
+                                if hungry.contains(entity) {
+                                    commands.entity(entity).remove::<Hungry>();
+                                } else {
+                                    commands.entity(entity).insert(Fertile);
+                                }
+                                for visited in history.0.iter() {
+                                    pheromones.deposit(
+                                        window,
+                                        visited.translation.x,
+                                        visited.translation.y,
+                                    );
+                                }
+                                history.0.clear();
+                                is_eaten.0 = true;
+                            }
+                        }
+                    } else if food_distance <= traits.sense {
+                        if prey.is_none() || food_distance >= prey.unwrap().distance {
+                            commands.entity(entity).insert(Prey {
+                                x: food.1.translation.x,
+                                y: food.1.translation.y,
+                                distance: food_distance,
+                            });
+                        }
+                    } else if prey.is_some() {
+                        commands.entity(entity).remove::<Prey>();
+                    }
+                }
+
+                if fertile.contains(entity) {
+                    goal.transition(AIGoal::Return);
+                }
+            }
+            AIGoal::Return => {
+                let d = options.movement_speed * FIXED_TIMESTEP as f32 * traits.speed;
+                let e = d * traits.speed * options.base_energy_cost;
+
+                let left = transform.translation.x + width;
+                let right = width - transform.translation.x;
+                let bottom = transform.translation.y + height;
+                let top = height - transform.translation.y;
+
+                let min = [left, right, bottom, top]
+                    .into_iter()
+                    .reduce(f32::min)
+                    .unwrap_or(0.);
+                if min <= 0. {
+                    goal.transition(AIGoal::AtHome);
+                } else if min == left {
+                    transform.translation.x -= d;
+                    transform.rotation = Quat::from_rotation_z(f32::to_radians(180.));
+                    energy.0 -= e;
+                } else if min == right {
+                    transform.translation.x += d;
+                    transform.rotation = Quat::from_rotation_z(f32::to_radians(0.));
+                    energy.0 -= e;
+                } else if min == bottom {
+                    transform.translation.y -= d;
+                    transform.rotation = Quat::from_rotation_z(f32::to_radians(270.));
+                    energy.0 -= e;
+                } else if min == top {
+                    transform.translation.y += d;
+                    transform.rotation = Quat::from_rotation_z(f32::to_radians(90.));
+                    energy.0 -= e;
+                }
+            }
         }
     }
 }
 
-fn home_movement(
-    time: Res<Time>,
+/// Predator counterpart of [`step_ai`]: `Seek` hunts the nearest person in
+/// range instead of sampling pheromones, but shares the same `Return`/
+/// `AtHome` homing behavior.
+fn step_ai_predator(
     mut commands: Commands,
-    mut sprites: Query<
-        (&mut Transform, Entity, &Traits, &mut Energy),
-        (With<Person>, With<Returning>, Without<Dead>),
+    mut predators: Query<
+        (
+            Entity,
+            &mut AIGoal,
+            &mut Transform,
+            &mut Energy,
+            &Traits,
+            Option<&Hunt>,
+        ),
+        With<Predator>,
     >,
+    persons: Query<(Entity, &Transform), With<Person>>,
+    hungry: Query<&Hungry>,
+    fertile: Query<&Fertile>,
+    sunset: Res<Sunset>,
     windows: Res<Windows>,
+    mut rng: ResMut<SimRng>,
     options: Res<Options>,
 ) {
     let window = windows.primary();
     let width = window.width() / 2.;
     let height = window.height() / 2.;
 
-    for mut sprite in sprites.iter_mut() {
-        let d = options.movement_speed
-            * time.delta_seconds()
-            * options.simulation_speed
-            * sprite.2.speed;
-        let e = d * sprite.2.speed * options.base_energy_cost;
-
-        let mut transform = sprite.0;
-        let left = transform.translation.x + width;
-        let right = width - transform.translation.x;
-        let bottom = transform.translation.y + height;
-        let top = height - transform.translation.y;
-
-        let min = [left, right, bottom, top]
-            .into_iter()
-            .reduce(f32::min)
-            .unwrap_or(0.);
-        if min <= 0. {
-            commands.entity(sprite.1).insert(AtHome);
-        } else if min == left {
-            transform.translation.x -= d;
-            transform.rotation = Quat::from_rotation_z(f32::to_radians(180.));
-            sprite.3 .0 -= e;
-        } else if min == right {
-            transform.translation.x += d;
-            transform.rotation = Quat::from_rotation_z(f32::to_radians(0.));
-            sprite.3 .0 -= e;
-        } else if min == bottom {
-            transform.translation.y -= d;
-            transform.rotation = Quat::from_rotation_z(f32::to_radians(270.));
-            sprite.3 .0 -= e;
-        } else if min == top {
-            transform.translation.y += d;
-            transform.rotation = Quat::from_rotation_z(f32::to_radians(90.));
-            sprite.3 .0 -= e;
-        }
-    }
-}
+    for (entity, mut goal, mut transform, mut energy, traits, hunt) in predators.iter_mut() {
+        match *goal {
+            AIGoal::AtHome | AIGoal::Dead => continue,
+            AIGoal::Seek => {
+                if sunset.0 {
+                    if !hungry.contains(entity) {
+                        goal.transition(AIGoal::Return);
+                    }
+                    continue;
+                }
 
-fn fertile_return(mut commands: Commands, entities: Query<Entity, With<Fertile>>) {
-    for entity in entities.iter() {
-        commands.entity(entity).insert(Returning);
-    }
-}
+                if let Some(hunt) = hunt {
+                    transform.rotation = Quat::from_rotation_z(
+                        (hunt.y - transform.translation.y).atan2(hunt.x - transform.translation.x),
+                    );
+                } else {
+                    let rotation_delta =
+                        Quat::from_rotation_z((rng.0.gen::<f32>() - 0.5) * 12. * FIXED_TIMESTEP as f32);
+                    transform.rotation *= rotation_delta;
+                }
 
-fn non_hungry_return(
-    mut commands: Commands,
-    entities: Query<Entity, (With<Person>, Without<Hungry>)>,
-) {
-    for entity in entities.iter() {
-        commands.entity(entity).insert(Returning);
-    }
-}
+                let rotation_rad = transform.rotation.to_euler(EulerRot::ZYX).0;
+                let distance = options.movement_speed * traits.speed * FIXED_TIMESTEP as f32;
+                energy.0 -= distance * traits.speed * options.predator_speed_cost
+                    + options.predator_sense_cost * FIXED_TIMESTEP as f32;
 
-fn get_distance(x1: f32, y1: f32, x2: f32, y2: f32) -> f32 {
-    ((x1 - x2).powi(2) + (y1 - y2).powi(2)).sqrt()
-}
+                transform.translation.x += distance * rotation_rad.cos();
+                transform.translation.y += distance * rotation_rad.sin();
 
-fn radar(
-    mut commands: Commands,
-    persons: Query<(Entity, &Transform, Option<&Prey>, &Traits), (With<Person>, Without<Fertile>)>,
-    foods: Query<(Entity, &Transform), With<Food>>,
-    mut eaten: Query<&mut Eaten>,
-    hungry: Query<&Hungry>,
-) {
-    for person in persons.iter() {
-        for food in foods.iter() {
-            let distance = get_distance(
-                person.1.translation.x,
-                person.1.translation.y,
-                food.1.translation.x,
-                food.1.translation.y,
-            );
-            if distance <= 45. {
-                if let Ok(mut is_eaten) = eaten.get_mut(food.0) {
-                    if !is_eaten.0 {
-                        commands.entity(food.0).despawn();
-                        commands.entity(food.0).remove::<Eaten>();
-
-                        if hungry.contains(person.0) {
-                            commands.entity(person.0).remove::<Hungry>();
+                if transform.translation.x > width {
+                    transform.translation.x = -width;
+                }
+                if transform.translation.x < -width {
+                    transform.translation.x = width;
+                }
+                if transform.translation.y > height {
+                    transform.translation.y = -height;
+                }
+                if transform.translation.y < -height {
+                    transform.translation.y = height;
+                }
+
+                let mut nearest: Option<(Entity, f32, f32, f32)> = None;
+                for person in persons.iter() {
+                    let person_distance = get_distance(
+                        transform.translation.x,
+                        transform.translation.y,
+                        person.1.translation.x,
+                        person.1.translation.y,
+                    );
+                    if person_distance > traits.sense {
+                        continue;
+                    }
+                    if nearest.map_or(true, |(_, _, _, d)| person_distance < d) {
+                        nearest = Some((
+                            person.0,
+                            person.1.translation.x,
+                            person.1.translation.y,
+                            person_distance,
+                        ));
+                    }
+                }
+
+                match nearest {
+                    Some((target, _x, _y, distance)) if distance <= options.predator_catch_radius => {
+                        commands.entity(target).despawn();
+                        commands.entity(entity).remove::<Hunt>();
+
+                        if hungry.contains(entity) {
+                            commands.entity(entity).remove::<Hungry>();
                         } else {
-                            commands.entity(person.0).insert(Fertile);
+                            commands.entity(entity).insert(Fertile);
+                        }
+                    }
+                    Some((_, x, y, _)) => {
+                        commands.entity(entity).insert(Hunt { x, y });
+                    }
+                    None => {
+                        if hunt.is_some() {
+                            commands.entity(entity).remove::<Hunt>();
                         }
-                        is_eaten.0 = true;
                     }
                 }
-            } else if distance <= person.3.sense {
-                if !person.2.is_some() || distance >= person.2.unwrap().distance {
-                    commands.entity(person.0).insert(Prey {
-                        x: food.1.translation.x,
-                        y: food.1.translation.y,
-                        distance: distance,
-                    });
+
+                if fertile.contains(entity) {
+                    goal.transition(AIGoal::Return);
                 }
-            } else {
-                if person.2.is_some() {
-                    commands.entity(person.0).remove::<Prey>();
+            }
+            AIGoal::Return => {
+                let d = options.movement_speed * FIXED_TIMESTEP as f32 * traits.speed;
+                let e = d * traits.speed * options.predator_speed_cost;
+
+                let left = transform.translation.x + width;
+                let right = width - transform.translation.x;
+                let bottom = transform.translation.y + height;
+                let top = height - transform.translation.y;
+
+                let min = [left, right, bottom, top]
+                    .into_iter()
+                    .reduce(f32::min)
+                    .unwrap_or(0.);
+                if min <= 0. {
+                    goal.transition(AIGoal::AtHome);
+                } else if min == left {
+                    transform.translation.x -= d;
+                    transform.rotation = Quat::from_rotation_z(f32::to_radians(180.));
+                    energy.0 -= e;
+                } else if min == right {
+                    transform.translation.x += d;
+                    transform.rotation = Quat::from_rotation_z(f32::to_radians(0.));
+                    energy.0 -= e;
+                } else if min == bottom {
+                    transform.translation.y -= d;
+                    transform.rotation = Quat::from_rotation_z(f32::to_radians(270.));
+                    energy.0 -= e;
+                } else if min == top {
+                    transform.translation.y += d;
+                    transform.rotation = Quat::from_rotation_z(f32::to_radians(90.));
+                    energy.0 -= e;
                 }
             }
         }
     }
 }
 
-fn day_timer(time: Res<Time>, mut timer: ResMut<DayTimer>, mut sunset: ResMut<Sunset>) {
+fn day_timer(mut timer: ResMut<DayTimer>, mut sunset: ResMut<Sunset>) {
     if !sunset.0 {
-        timer.0.tick(time.delta());
+        timer.0.tick(Duration::from_secs_f32(FIXED_TIMESTEP as f32));
         if timer.0.finished() {
             sunset.0 = true;
         }
@@ -385,37 +817,56 @@ fn day_timer(time: Res<Time>, mut timer: ResMut<DayTimer>, mut sunset: ResMut<Su
 }
 
 fn night_timer(
-    time: Res<Time>,
     mut commands: Commands,
     mut timer: ResMut<NightTimer>,
     mut sunset: ResMut<Sunset>,
-    to_die: Query<Entity, (With<Person>, Without<AtHome>)>,
-    mut to_live: Query<(Entity, &mut Energy), (With<Person>, With<AtHome>)>,
-    to_reproduce: Query<(&Transform, &Traits), (With<Person>, With<AtHome>, With<Fertile>)>,
+    mut agents: Query<
+        (
+            Entity,
+            &mut Energy,
+            &mut AIGoal,
+            &Transform,
+            &Traits,
+            Option<&Fertile>,
+            Option<&Person>,
+            Option<&Predator>,
+        ),
+        Or<(With<Person>, With<Predator>)>,
+    >,
     mut ev_randomize: EventWriter<RandomizeDirections>,
     mut ev_spawn_food: EventWriter<SpawnFood>,
     mut ev_reproduce: EventWriter<Reproduce>,
+    mut ev_reproduce_predator: EventWriter<ReproducePredator>,
+    mut rng: ResMut<SimRng>,
+    mut stats: ResMut<RunStats>,
     options: Res<Options>,
 ) {
-    timer.0.tick(time.delta());
+    timer.0.tick(Duration::from_secs_f32(FIXED_TIMESTEP as f32));
     if timer.0.finished() {
         sunset.0 = false;
+        stats.generations += 1;
 
-        for person in to_die.iter() {
-            commands.entity(person).despawn();
-        }
-        for mut person in to_live.iter_mut() {
-            person.1 .0 = options.base_energy;
-            commands.entity(person.0).insert(Hungry);
-            commands
-                .entity(person.0)
-                .remove_bundle::<(Fertile, Returning, AtHome)>();
-        }
-        for person in to_reproduce.iter() {
-            ev_reproduce.send(Reproduce(
-                *person.0,
-                person.1.variation(options.trait_change_intensity),
-            ));
+        for (entity, mut energy, mut goal, transform, traits, fertile, person, predator) in
+            agents.iter_mut()
+        {
+            if *goal != AIGoal::AtHome {
+                commands.entity(entity).despawn();
+                continue;
+            }
+
+            if fertile.is_some() {
+                let offspring = traits.variation(options.trait_change_intensity, &mut rng.0);
+                if person.is_some() {
+                    ev_reproduce.send(Reproduce(*transform, offspring));
+                } else if predator.is_some() {
+                    ev_reproduce_predator.send(ReproducePredator(*transform, offspring));
+                }
+            }
+
+            energy.0 = options.base_energy;
+            commands.entity(entity).insert(Hungry);
+            commands.entity(entity).remove::<Fertile>();
+            goal.transition(AIGoal::Seek);
         }
         ev_randomize.send(RandomizeDirections);
         ev_spawn_food.send(SpawnFood);
@@ -436,6 +887,29 @@ fn reproduce(
                 ..default()
             })
             .insert(Person)
+            .insert(AIGoal::Seek)
+            .insert(Hungry)
+            .insert(Energy(options.base_energy))
+            .insert(event.1)
+            .insert(History::default());
+    }
+}
+
+fn reproduce_predator(
+    mut commands: Commands,
+    mut events: EventReader<ReproducePredator>,
+    asset_server: Res<AssetServer>,
+    options: Res<Options>,
+) {
+    for event in events.iter() {
+        commands
+            .spawn_bundle(SpriteBundle {
+                texture: asset_server.load("predator1.png"),
+                transform: event.0,
+                ..default()
+            })
+            .insert(Predator)
+            .insert(AIGoal::Seek)
             .insert(Hungry)
             .insert(Energy(options.base_energy))
             .insert(event.1);
@@ -444,11 +918,13 @@ fn reproduce(
 
 fn count_stuff(
     mut events: EventReader<RandomizeDirections>,
-    persons: Query<&Traits>,
+    persons: Query<&Traits, With<Person>>,
+    predators: Query<&Traits, With<Predator>>,
     foods: Query<&Food>,
     time: Res<Time>,
     mut charts: ResMut<Charts>,
-    mut exit: EventWriter<bevy::app::AppExit>,
+    mut stats: ResMut<RunStats>,
+    mut state: ResMut<State<GameState>>,
     options: Res<Options>,
 ) {
     for _event in events.iter() {
@@ -461,12 +937,25 @@ fn count_stuff(
             people_count += 1.;
         }
         let food_count = foods.iter().count();
+        stats.peak_population = stats.peak_population.max(people_count as i32);
         if people_count <= 0. {
-            exit.send(bevy::app::AppExit);
+            let _ = state.set(GameState::Extinct);
             break;
         }
         speed_avg = speed_avg / people_count;
         sense_avg = sense_avg / people_count;
+        stats.final_avg_speed = speed_avg;
+        stats.final_avg_sense = sense_avg;
+
+        let mut predator_speed_avg = 0.;
+        let mut predator_count = 0.;
+        for predator in predators.iter() {
+            predator_speed_avg += predator.speed;
+            predator_count += 1.;
+        }
+        if predator_count > 0. {
+            predator_speed_avg = predator_speed_avg / predator_count;
+        }
 
         println!("{};\t{};\t{};", people_count, food_count, speed_avg);
 
@@ -492,6 +981,17 @@ fn count_stuff(
             x: time.seconds_since_startup() * options.simulation_speed as f64,
             y: sense_avg as f64,
         });
+        charts.predator_population.push(Bar {
+            argument: time.seconds_since_startup() * options.simulation_speed as f64,
+            value: predator_count as f64,
+            name: String::from("Predator Population"),
+            fill: egui::Color32::DARK_RED,
+            ..bar_options()
+        });
+        charts.avg_predator_speed.push(Value {
+            x: time.seconds_since_startup() * options.simulation_speed as f64,
+            y: predator_speed_avg as f64,
+        });
     }
 }
 
@@ -501,6 +1001,9 @@ fn plot_stuff(mut context: ResMut<EguiContext>, charts: Res<Charts>) {
         let food_chart = BarChart::new(charts.food_count.clone());
         let avg_speed_line = Line::new(Values::from_values(charts.avg_speed.clone()));
         let avg_sense_line = Line::new(Values::from_values(charts.avg_sense.clone()));
+        let predator_population_chart = BarChart::new(charts.predator_population.clone());
+        let avg_predator_speed_line =
+            Line::new(Values::from_values(charts.avg_predator_speed.clone()));
         Plot::new("Stats_1").height(200.).show(ui, |plot_ui| {
             plot_ui.bar_chart(population_chart);
             plot_ui.bar_chart(food_chart);
@@ -511,16 +1014,22 @@ fn plot_stuff(mut context: ResMut<EguiContext>, charts: Res<Charts>) {
         Plot::new("Stats_3")
             .height(200.)
             .show(ui, |plot_ui| plot_ui.line(avg_sense_line));
+        Plot::new("Stats_4")
+            .height(200.)
+            .show(ui, |plot_ui| plot_ui.bar_chart(predator_population_chart));
+        Plot::new("Stats_5")
+            .height(200.)
+            .show(ui, |plot_ui| plot_ui.line(avg_predator_speed_line));
     });
 }
 
 fn options_window(
     mut context: ResMut<EguiContext>,
-    mut started: ResMut<Started>,
+    mut state: ResMut<State<GameState>>,
     mut options: ResMut<Options>,
 ) {
     egui::Window::new("Options")
-        .enabled(!started.0)
+        .enabled(*state.current() == GameState::Configuring)
         .show(context.ctx_mut(), |ui| {
             ui.label("Simulation speed:");
             ui.add(
@@ -553,12 +1062,328 @@ fn options_window(
             ui.label("Food count:");
             ui.add(DragValue::new(&mut options.food_count).speed(10));
 
+            ui.label("Predator count:");
+            ui.add(DragValue::new(&mut options.predator_count).speed(1));
+
+            ui.label("Predator speed cost:");
+            ui.add(DragValue::new(&mut options.predator_speed_cost).speed(0.0001));
+
+            ui.label("Predator sense cost:");
+            ui.add(DragValue::new(&mut options.predator_sense_cost).speed(0.0001));
+
+            ui.label("Predator catch radius:");
+            ui.add(DragValue::new(&mut options.predator_catch_radius).speed(1));
+
+            ui.label("RNG seed:");
+            ui.add(DragValue::new(&mut options.rng_seed).speed(1));
+
             if ui.button("Start Simulation").clicked() {
-                started.0 = true;
+                let _ = state.set(GameState::Running);
             }
         });
 }
 
+fn save_load_window(
+    mut context: ResMut<EguiContext>,
+    mut save_file: ResMut<SaveFile>,
+    mut ev_save: EventWriter<SaveRequested>,
+    mut ev_load: EventWriter<LoadRequested>,
+) {
+    egui::Window::new("Save/Load").show(context.ctx_mut(), |ui| {
+        ui.label("Save file:");
+        ui.text_edit_singleline(&mut save_file.0);
+
+        if ui.button("Save").clicked() {
+            ev_save.send(SaveRequested);
+        }
+        if ui.button("Load").clicked() {
+            ev_load.send(LoadRequested);
+        }
+    });
+}
+
+fn save_simulation(
+    mut events: EventReader<SaveRequested>,
+    save_file: Res<SaveFile>,
+    options: Res<Options>,
+    sunset: Res<Sunset>,
+    day_timer: Res<DayTimer>,
+    night_timer: Res<NightTimer>,
+    charts: Res<Charts>,
+    persons: Query<
+        (
+            &Transform,
+            &Energy,
+            &Traits,
+            &AIGoal,
+            Option<&Hungry>,
+            Option<&Fertile>,
+        ),
+        With<Person>,
+    >,
+    predators: Query<
+        (
+            &Transform,
+            &Energy,
+            &Traits,
+            &AIGoal,
+            Option<&Hungry>,
+            Option<&Fertile>,
+        ),
+        With<Predator>,
+    >,
+) {
+    for _event in events.iter() {
+        let snapshot = SimulationSnapshot {
+            options: options.clone(),
+            sunset: sunset.0,
+            day_timer_elapsed: day_timer.0.elapsed_secs(),
+            night_timer_elapsed: night_timer.0.elapsed_secs(),
+            population: charts
+                .population
+                .iter()
+                .map(|bar| ChartPoint {
+                    x: bar.argument,
+                    y: bar.value,
+                })
+                .collect(),
+            food_count: charts
+                .food_count
+                .iter()
+                .map(|bar| ChartPoint {
+                    x: bar.argument,
+                    y: bar.value,
+                })
+                .collect(),
+            avg_speed: charts
+                .avg_speed
+                .iter()
+                .map(|value| ChartPoint {
+                    x: value.x,
+                    y: value.y,
+                })
+                .collect(),
+            avg_sense: charts
+                .avg_sense
+                .iter()
+                .map(|value| ChartPoint {
+                    x: value.x,
+                    y: value.y,
+                })
+                .collect(),
+            predator_population: charts
+                .predator_population
+                .iter()
+                .map(|bar| ChartPoint {
+                    x: bar.argument,
+                    y: bar.value,
+                })
+                .collect(),
+            avg_predator_speed: charts
+                .avg_predator_speed
+                .iter()
+                .map(|value| ChartPoint {
+                    x: value.x,
+                    y: value.y,
+                })
+                .collect(),
+            persons: persons
+                .iter()
+                .map(|(transform, energy, traits, goal, hungry, fertile)| AgentSnapshot {
+                    x: transform.translation.x,
+                    y: transform.translation.y,
+                    rotation: transform.rotation.to_euler(EulerRot::ZYX).0,
+                    energy: energy.0,
+                    traits: *traits,
+                    goal: *goal,
+                    hungry: hungry.is_some(),
+                    fertile: fertile.is_some(),
+                })
+                .collect(),
+            predators: predators
+                .iter()
+                .map(|(transform, energy, traits, goal, hungry, fertile)| AgentSnapshot {
+                    x: transform.translation.x,
+                    y: transform.translation.y,
+                    rotation: transform.rotation.to_euler(EulerRot::ZYX).0,
+                    energy: energy.0,
+                    traits: *traits,
+                    goal: *goal,
+                    hungry: hungry.is_some(),
+                    fertile: fertile.is_some(),
+                })
+                .collect(),
+        };
+
+        match ron::ser::to_string_pretty(&snapshot, ron::ser::PrettyConfig::default()) {
+            Ok(serialized) => {
+                if let Err(error) = std::fs::write(&save_file.0, serialized) {
+                    println!("Failed to save simulation to {}: {}", save_file.0, error);
+                }
+            }
+            Err(error) => println!("Failed to serialize simulation: {}", error),
+        }
+    }
+}
+
+/// Restores the full population, including each agent's [`AIGoal`] and
+/// `Hungry`/`Fertile` state, so a reload resumes the same day at the same
+/// feeding progress rather than kicking every agent back to `Seek`+`Hungry`.
+/// The RNG stream doesn't resume: [`SimRng`] is reseeded from
+/// `options.rng_seed`, so post-load draws diverge from the original run.
+/// Food is despawned and not respawned until the next night ends (see
+/// [`SimulationSnapshot`] for everything a load leaves out).
+fn load_simulation(
+    mut commands: Commands,
+    mut events: EventReader<LoadRequested>,
+    save_file: Res<SaveFile>,
+    asset_server: Res<AssetServer>,
+    mut options: ResMut<Options>,
+    mut sunset: ResMut<Sunset>,
+    mut day_timer: ResMut<DayTimer>,
+    mut night_timer: ResMut<NightTimer>,
+    mut charts: ResMut<Charts>,
+    mut state: ResMut<State<GameState>>,
+    mut loaded: ResMut<Loaded>,
+    mut rng: ResMut<SimRng>,
+    existing: Query<Entity, Or<(With<Person>, With<Predator>, With<Food>)>>,
+) {
+    for _event in events.iter() {
+        let contents = match std::fs::read_to_string(&save_file.0) {
+            Ok(contents) => contents,
+            Err(error) => {
+                println!("Failed to read {}: {}", save_file.0, error);
+                continue;
+            }
+        };
+        let snapshot: SimulationSnapshot = match ron::de::from_str(&contents) {
+            Ok(snapshot) => snapshot,
+            Err(error) => {
+                println!("Failed to parse {}: {}", save_file.0, error);
+                continue;
+            }
+        };
+
+        for entity in existing.iter() {
+            commands.entity(entity).despawn();
+        }
+
+        *options = snapshot.options;
+        sunset.0 = snapshot.sunset;
+        *rng = SimRng::from_seed(options.rng_seed);
+
+        day_timer.0 = Timer::from_seconds(options.day_length, true);
+        day_timer
+            .0
+            .set_elapsed(Duration::from_secs_f32(snapshot.day_timer_elapsed));
+        night_timer.0 = Timer::from_seconds(options.day_length + options.night_length, true);
+        night_timer
+            .0
+            .set_elapsed(Duration::from_secs_f32(snapshot.night_timer_elapsed));
+
+        charts.population = snapshot
+            .population
+            .iter()
+            .map(|point| Bar {
+                argument: point.x,
+                value: point.y,
+                name: String::from("Population"),
+                ..bar_options()
+            })
+            .collect();
+        charts.food_count = snapshot
+            .food_count
+            .iter()
+            .map(|point| Bar {
+                argument: point.x,
+                value: point.y,
+                name: String::from("Food Count"),
+                fill: egui::Color32::RED,
+                bar_width: 1.,
+                ..bar_options()
+            })
+            .collect();
+        charts.avg_speed = snapshot
+            .avg_speed
+            .iter()
+            .map(|point| Value {
+                x: point.x,
+                y: point.y,
+            })
+            .collect();
+        charts.avg_sense = snapshot
+            .avg_sense
+            .iter()
+            .map(|point| Value {
+                x: point.x,
+                y: point.y,
+            })
+            .collect();
+        charts.predator_population = snapshot
+            .predator_population
+            .iter()
+            .map(|point| Bar {
+                argument: point.x,
+                value: point.y,
+                name: String::from("Predator Population"),
+                fill: egui::Color32::DARK_RED,
+                ..bar_options()
+            })
+            .collect();
+        charts.avg_predator_speed = snapshot
+            .avg_predator_speed
+            .iter()
+            .map(|point| Value {
+                x: point.x,
+                y: point.y,
+            })
+            .collect();
+
+        for agent in snapshot.persons.iter() {
+            let mut entity = commands.spawn_bundle(SpriteBundle {
+                texture: asset_server.load("person1.png"),
+                transform: Transform::from_xyz(agent.x, agent.y, 0.)
+                    .with_rotation(Quat::from_rotation_z(agent.rotation)),
+                ..default()
+            });
+            entity
+                .insert(Person)
+                .insert(agent.goal)
+                .insert(Energy(agent.energy))
+                .insert(agent.traits)
+                .insert(History::default());
+            if agent.hungry {
+                entity.insert(Hungry);
+            }
+            if agent.fertile {
+                entity.insert(Fertile);
+            }
+        }
+        for agent in snapshot.predators.iter() {
+            let mut entity = commands.spawn_bundle(SpriteBundle {
+                texture: asset_server.load("predator1.png"),
+                transform: Transform::from_xyz(agent.x, agent.y, 0.)
+                    .with_rotation(Quat::from_rotation_z(agent.rotation)),
+                ..default()
+            });
+            entity
+                .insert(Predator)
+                .insert(agent.goal)
+                .insert(Energy(agent.energy))
+                .insert(agent.traits);
+            if agent.hungry {
+                entity.insert(Hungry);
+            }
+            if agent.fertile {
+                entity.insert(Fertile);
+            }
+        }
+
+        let _ = state.set(GameState::Running);
+        loaded.0 = true;
+    }
+}
+
 fn background_color(sunset: Res<Sunset>, mut clear_color: ResMut<ClearColor>) {
     if sunset.0 {
         clear_color.0 = Color::rgb(0.5, 0.4, 0.4);
@@ -569,46 +1394,133 @@ fn background_color(sunset: Res<Sunset>, mut clear_color: ResMut<ClearColor>) {
 
 fn randomize_directions(
     mut events: EventReader<RandomizeDirections>,
-    mut persons: Query<&mut Transform, With<Person>>,
+    mut persons: Query<&mut Transform, Or<(With<Person>, With<Predator>)>>,
+    mut rng: ResMut<SimRng>,
 ) {
-    let mut rng = rand::thread_rng();
     for _event in events.iter() {
         for mut person in persons.iter_mut() {
-            person.rotation = Quat::from_rotation_z(f32::to_radians(rng.gen::<f32>() * 360.))
+            person.rotation = Quat::from_rotation_z(f32::to_radians(rng.0.gen::<f32>() * 360.))
         }
     }
 }
 
-fn energy(mut commands: Commands, people: Query<(Entity, &Energy), Without<Dead>>) {
-    for person in people.iter() {
-        if person.1 .0 <= 0. {
-            commands.entity(person.0).insert(Dead);
+fn energy(mut people: Query<(&Energy, &mut AIGoal)>) {
+    for (energy, mut goal) in people.iter_mut() {
+        if energy.0 <= 0. {
+            goal.transition(AIGoal::Dead);
         }
     }
 }
 
-fn run_if_sunset(sunset: Res<Sunset>) -> ShouldRun {
-    if sunset.0 {
+fn run_if_running(state: Res<State<GameState>>) -> ShouldRun {
+    if *state.current() == GameState::Running && !state.is_changed() {
         ShouldRun::Yes
     } else {
         ShouldRun::No
     }
 }
 
-fn run_if_day(sunset: Res<Sunset>) -> ShouldRun {
-    if !sunset.0 {
-        ShouldRun::Yes
-    } else {
+fn and_if_running(In(input): In<ShouldRun>, state: Res<State<GameState>>) -> ShouldRun {
+    if matches!(input, ShouldRun::No)
+        || *state.current() != GameState::Running
+        || state.is_changed()
+    {
         ShouldRun::No
+    } else {
+        ShouldRun::Yes
     }
 }
 
-fn run_if_started(started: Res<Started>) -> ShouldRun {
-    if started.0 && !started.is_changed() {
-        ShouldRun::Yes
+/// Runs a fixed-step stage `options.simulation_speed` times per real tick on
+/// average, instead of scaling its per-step math, so `simulation_speed` only
+/// changes how fast wall-clock time passes, never the number of fixed steps
+/// (and therefore RNG draws) a simulated day takes. A fractional accumulator
+/// (Bresenham-style) carries over sub-1x speeds across ticks, so a speed
+/// like 0.5 runs the stage every other tick instead of being floored to 1x.
+fn repeat_for_speed(
+    In(input): In<ShouldRun>,
+    options: Res<Options>,
+    mut remaining: Local<usize>,
+    mut accumulator: Local<f32>,
+) -> ShouldRun {
+    if matches!(input, ShouldRun::No) {
+        *remaining = 0;
+        *accumulator = 0.;
+        return ShouldRun::No;
+    }
+
+    if *remaining == 0 {
+        *accumulator += options.simulation_speed.max(0.);
+        *remaining = accumulator.floor() as usize;
+        if *remaining == 0 {
+            return ShouldRun::No;
+        }
+        *accumulator -= *remaining as f32;
+    }
+    *remaining -= 1;
+
+    if *remaining > 0 {
+        ShouldRun::YesAndCheckAgain
     } else {
-        ShouldRun::No
+        ShouldRun::Yes
+    }
+}
+
+/// Pressing Space pushes `Paused` on top of `Running` (freezing every system
+/// gated on `GameState::Running` without losing it), or pops back off.
+fn pause_toggle(keyboard: Res<Input<KeyCode>>, mut state: ResMut<State<GameState>>) {
+    if !keyboard.just_pressed(KeyCode::Space) {
+        return;
+    }
+    match state.current() {
+        GameState::Running => {
+            let _ = state.push(GameState::Paused);
+        }
+        GameState::Paused => {
+            let _ = state.pop();
+        }
+        _ => {}
+    }
+}
+
+fn extinction_window(
+    mut context: ResMut<EguiContext>,
+    mut commands: Commands,
+    mut state: ResMut<State<GameState>>,
+    mut loaded: ResMut<Loaded>,
+    mut charts: ResMut<Charts>,
+    mut stats: ResMut<RunStats>,
+    mut pheromones: ResMut<Pheromones>,
+    mut sunset: ResMut<Sunset>,
+    mut exit: EventWriter<bevy::app::AppExit>,
+    existing: Query<Entity, Or<(With<Person>, With<Predator>, With<Food>)>>,
+) {
+    if *state.current() != GameState::Extinct {
+        return;
     }
+
+    egui::Window::new("Extinction").show(context.ctx_mut(), |ui| {
+        ui.label("The population has gone extinct.");
+        ui.label(format!("Generations survived: {}", stats.generations));
+        ui.label(format!("Peak population: {}", stats.peak_population));
+        ui.label(format!("Final average speed: {:.2}", stats.final_avg_speed));
+        ui.label(format!("Final average sense: {:.2}", stats.final_avg_sense));
+
+        if ui.button("Restart").clicked() {
+            for entity in existing.iter() {
+                commands.entity(entity).despawn();
+            }
+            *charts = Charts::default();
+            *stats = RunStats::default();
+            *pheromones = Pheromones::default();
+            sunset.0 = false;
+            loaded.0 = false;
+            let _ = state.set(GameState::Configuring);
+        }
+        if ui.button("Quit").clicked() {
+            exit.send(bevy::app::AppExit);
+        }
+    });
 }
 
 //fn debug1(query: Query<Entity, Without<Dead>>) {
@@ -624,56 +1536,76 @@ fn main() {
         .insert_resource(ClearColor(Color::rgb(0.9, 0.8, 0.8)))
         .insert_resource(Sunset(false))
         .insert_resource(Charts::default())
-        .insert_resource(Started(false))
+        .insert_resource(Pheromones::default())
+        .insert_resource(RunStats::default())
+        .insert_resource(Loaded(false))
+        .insert_resource(SaveFile::default())
+        .insert_resource(SimRng::from_seed(options.rng_seed))
         .insert_resource(Options::default())
         .insert_resource(DayTimer {
-            0: Timer::from_seconds(options.day_length / options.simulation_speed, true),
+            0: Timer::from_seconds(options.day_length, true),
         })
         .insert_resource(NightTimer {
-            0: Timer::from_seconds(
-                (options.night_length + options.day_length) / options.simulation_speed,
-                true,
-            ),
+            0: Timer::from_seconds(options.night_length + options.day_length, true),
         })
         .add_plugins(DefaultPlugins)
         .add_plugin(EguiPlugin)
+        .add_state(GameState::Configuring)
         .add_startup_system(setup)
         .add_system(start_simulation)
         .add_system(background_color)
-        .add_system(home_movement)
-        .add_system_set(
-            SystemSet::new()
-                .with_run_criteria(run_if_sunset)
-                .with_system(non_hungry_return),
-        )
+        .add_system(pause_toggle)
         .add_system_set(
             SystemSet::new()
-                .with_run_criteria(run_if_day)
-                .with_system(normal_movement)
-                .with_system(normal_rotation)
-                .with_system(radar),
+                .with_run_criteria(
+                    FixedTimestep::step(FIXED_TIMESTEP)
+                        .chain(and_if_running)
+                        .chain(repeat_for_speed),
+                )
+                .with_system(step_ai)
+                .with_system(step_ai_predator)
+                .with_system(decay_pheromones),
         )
         .add_system_set_to_stage(
             CoreStage::PreUpdate,
             SystemSet::new()
-                .with_run_criteria(run_if_started)
+                .with_run_criteria(
+                    FixedTimestep::step(FIXED_TIMESTEP)
+                        .chain(and_if_running)
+                        .chain(repeat_for_speed),
+                )
                 .with_system(night_timer),
         )
         .add_system_set(
             SystemSet::new()
-                .with_run_criteria(run_if_started)
-                .with_system(day_timer)
+                .with_run_criteria(
+                    FixedTimestep::step(FIXED_TIMESTEP)
+                        .chain(and_if_running)
+                        .chain(repeat_for_speed),
+                )
+                .with_system(day_timer),
+        )
+        .add_system_set(
+            SystemSet::new()
+                .with_run_criteria(run_if_running)
                 .with_system(count_stuff),
         )
-        .add_system(fertile_return)
         .add_system(randomize_directions)
         .add_system(spawn_food)
         .add_system(reproduce)
+        .add_system(reproduce_predator)
         .add_system(energy)
         .add_system(plot_stuff)
         .add_system(options_window)
+        .add_system(save_load_window)
+        .add_system(save_simulation)
+        .add_system(load_simulation)
+        .add_system(extinction_window)
         .add_event::<RandomizeDirections>()
         .add_event::<SpawnFood>()
         .add_event::<Reproduce>()
+        .add_event::<ReproducePredator>()
+        .add_event::<SaveRequested>()
+        .add_event::<LoadRequested>()
         .run();
 }